@@ -5,6 +5,8 @@ use nom::{
     combinator::opt
 };
 
+use wasm_bindgen::prelude::*;
+
 use std::collections::HashMap;
 use std::sync::Mutex;
 use core::fmt::Debug;
@@ -38,7 +40,10 @@ impl LUT {
             let kv: Vec<&str> = line.split_whitespace().collect();
             let k = kv[0].to_string()
                 .chars()
-                .map(|c| c.to_digit(2).unwrap() as u8).collect();
+                .map(|c| match c {
+                    '-' => 2u8, // don't-care literal, carried through as-is
+                    d => d.to_digit(2).unwrap() as u8
+                }).collect();
             let v = isize::from_str_radix(kv[1],2).unwrap() as u8;
             lut.mappings.insert(k, v);
         }
@@ -46,23 +51,66 @@ impl LUT {
         lut
     }
 
+    /// "disassembles" the truth table back into a sum-of-products boolean
+    /// expression over the input names. Each on-set row becomes a product term
+    /// AND-ing every input that is `1` with the negation of every input that is
+    /// `0`; `-` positions are don't-cares and contribute nothing. The terms are
+    /// OR-ed together; an empty on-set renders as the constant `0`.
+    pub fn to_boolean_expr(&self) -> String {
+        let mut rows: Vec<&Vec<u8>> = self.mappings.iter()
+            .filter(|(_, &v)| v == 1)
+            .map(|(k, _)| k)
+            .collect();
+        rows.sort();
+
+        let mut terms: Vec<String> = vec!();
+        for row in rows {
+            let mut literals: Vec<String> = vec!();
+            for (i, &bit) in row.iter().enumerate() {
+                let name = &self.inputs[i].name;
+                match bit {
+                    1 => literals.push(name.clone()),
+                    0 => literals.push(format!("~{}", name)),
+                    _ => {} // don't-care contributes no literal
+                }
+            }
+            if literals.is_empty() {
+                terms.push("1".to_string());
+            } else {
+                terms.push(format!("({})", literals.join(" & ")));
+            }
+        }
+
+        if terms.is_empty() {
+            format!("{} = 0", self.output.name)
+        } else {
+            format!("{} = {}", self.output.name, terms.join(" | "))
+        }
+    }
+
     /// executes the LUT, setting the output signal based on current input
-    fn exec(self) {
+    fn exec(&self) {
 
         let mut signals: Vec<u8> = vec!();
-        for var in self.inputs {
+        for var in &self.inputs {
+            // a signal never driven this cycle defaults to 0
             match STATE.lock().unwrap().get(&var.name) { // TODO: .lock().unwrap() as a macro possible?
                 Some(&val) => signals.push(val),
-                None => panic!("var '{}' was not initialized", var.name)
+                None => signals.push(0)
             };
         }
 
-        match self.mappings.get(&signals) {
-            Some(&v) => {
-                STATE.lock().unwrap().insert(self.output.name, v);
-            },
-            None => {}
-        };
+        // BLIF `.names` semantics: a listed combination drives the given value,
+        // every other combination drives the default 0. Writing 0 on the off-set
+        // lets outputs fall back to 0 across cycles instead of latching stale.
+        // Rows carry `-` positions as `2` (don't-care), which match any input, so
+        // scan the rows with masking rather than an exact key lookup.
+        let v = self.mappings.iter()
+            .find(|(pattern, _)| pattern.iter().zip(&signals)
+                                        .all(|(&p, &s)| p == 2 || p == s))
+            .map(|(_, &v)| v)
+            .unwrap_or(0);
+        STATE.lock().unwrap().insert(self.output.name.clone(), v);
     }
 }
 
@@ -110,10 +158,50 @@ impl Register {
         }
     }
 
-    fn exec(self) {
-        // TODO: handle varying clock triggers if possible
-        let &i = STATE.lock().unwrap().get(&self.input.name).unwrap();
-        STATE.lock().unwrap().insert(self.output.name, i);
+    /// commits the register's next output for this cycle given the data value
+    /// `input` sampled at the cycle boundary. The previous value of the control
+    /// net and a "has been reset" flag are kept in `STATE` under keys derived
+    /// from the output name so edge detection survives across steps. The first
+    /// evaluation after reset drives the output from `init`; afterwards the
+    /// five BLIF trigger types determine when the data reaches the output: the
+    /// edge-triggered `re`/`fe`/`as` types latch the sampled `input`, while the
+    /// level-sensitive `ah`/`al` types follow the data net's current value.
+    fn exec(&self, input: u8) {
+        let mut state = STATE.lock().unwrap();
+        let init_key = format!("{}$init", self.output.name);
+        let prev_key = format!("{}$prev", self.output.name);
+
+        let initialized = state.get(&init_key).copied().unwrap_or(0);
+        let control = state.get(&self.control.name).copied().unwrap_or(0);
+        let prev = state.get(&prev_key).copied().unwrap_or(0);
+        let current = state.get(&self.output.name).copied().unwrap_or(0);
+        // a transparent latch follows the input computed this cycle, so the
+        // `ah`/`al` branches read it now (after the LUT pass) rather than the
+        // value `step` sampled at the cycle boundary, which the edge-triggered
+        // `re`/`fe`/`as` branches still use.
+        let live = state.get(&self.input.name).copied().unwrap_or(0);
+
+        let next = if initialized == 0 {
+            // reset: 0 = lo, 1 = hi, 2 = don't-care / 3 = unknown keep existing
+            match self.init {
+                0 => 0,
+                1 => 1,
+                _ => current
+            }
+        } else {
+            match self.signal.as_str() {
+                "re" => if prev == 0 && control == 1 { input } else { current }, // rising edge
+                "fe" => if prev == 1 && control == 0 { input } else { current }, // falling edge
+                "ah" => if control == 1 { live } else { current },              // active-high latch
+                "al" => if control == 0 { live } else { current },              // active-low latch
+                "as" => if prev != control { input } else { current },          // asynchronous
+                _    => input
+            }
+        };
+
+        state.insert(self.output.name.clone(), next);
+        state.insert(prev_key, control);
+        state.insert(init_key, 1);
     }
 }
 
@@ -160,6 +248,190 @@ impl Model {
             elements
         }
     }
+
+    /// orders the combinational `LUT`s so every element runs after the elements
+    /// that drive its inputs. Implemented as Kahn's algorithm over the
+    /// producer/consumer graph: an in-degree keyed by element index counts how
+    /// many of a LUT's inputs are produced by other LUTs, and zero-in-degree
+    /// nodes are emitted while their consumers are decremented. A node left with
+    /// a nonzero in-degree after the queue drains is part of a combinational
+    /// loop, which is reported rather than spun on.
+    fn topo_order(&self) -> Result<Vec<usize>, String> {
+
+        // signal name -> index of the LUT that drives it
+        let mut producer: HashMap<&str, usize> = HashMap::new();
+        for (i, el) in self.elements.iter().enumerate() {
+            if let Element::LUT(l) = el {
+                producer.insert(l.output.name.as_str(), i);
+            }
+        }
+
+        let mut in_degree: HashMap<usize, usize> = HashMap::new();
+        let mut consumers: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (i, el) in self.elements.iter().enumerate() {
+            if let Element::LUT(l) = el {
+                in_degree.entry(i).or_insert(0);
+                for var in &l.inputs {
+                    match producer.get(var.name.as_str()) {
+                        Some(&p) if p != i => {
+                            *in_degree.entry(i).or_insert(0) += 1;
+                            consumers.entry(p).or_insert_with(Vec::new).push(i);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // seed the queue with every LUT that reads no other LUT's output
+        let mut queue: Vec<usize> = in_degree.iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(&i, _)| i)
+            .collect();
+        queue.sort_unstable(); // keep evaluation order deterministic
+
+        let mut order: Vec<usize> = vec!();
+        let mut head = 0;
+        while head < queue.len() {
+            let n = queue[head];
+            head += 1;
+            order.push(n);
+            if let Some(cs) = consumers.get(&n) {
+                for &c in cs {
+                    let d = in_degree.get_mut(&c).unwrap();
+                    *d -= 1;
+                    if *d == 0 { queue.push(c); }
+                }
+            }
+        }
+
+        if order.len() != in_degree.len() {
+            return Err(format!("combinational cycle detected in model '{}'", self.name));
+        }
+        Ok(order)
+    }
+
+    /// renders the flattened model as a standalone Graphviz `digraph`. Pipe the
+    /// result into `dot` to visualise the circuit.
+    pub fn to_dot(&self) -> String {
+        format!("digraph \"{}\" {{\n{}}}\n",
+                Self::dot_escape(&self.name),
+                self.dot_body(""))
+    }
+
+    /// emits the node and edge statements for this model, prefixing every node
+    /// id with `prefix` so multiple models can share one enclosing graph
+    /// without colliding. Shared by `to_dot` and `Config::to_dot`.
+    fn dot_body(&self, prefix: &str) -> String {
+        let node = |name: &str| format!("\"{}{}\"", prefix, Self::dot_escape(name));
+        let elem = |i: usize| format!("\"{}n{}\"", prefix, i);
+
+        let mut out = String::new();
+
+        // top-level IO gets distinct shapes
+        for v in &self.inputs {
+            out.push_str(&format!("  {} [shape=invhouse,label=\"{}\"];\n",
+                                  node(&v.name), Self::dot_escape(&v.name)));
+        }
+        for v in &self.outputs {
+            out.push_str(&format!("  {} [shape=house,label=\"{}\"];\n",
+                                  node(&v.name), Self::dot_escape(&v.name)));
+        }
+
+        // one node per element, remembering which node drives each signal
+        let mut producer: HashMap<&str, String> = HashMap::new();
+        for v in &self.inputs {
+            producer.insert(v.name.as_str(), node(&v.name));
+        }
+        for (i, el) in self.elements.iter().enumerate() {
+            match el {
+                Element::LUT(l) => {
+                    out.push_str(&format!("  {} [shape=ellipse,label=\"{}\\n{}\"];\n",
+                                          elem(i), Self::dot_escape(&l.output.name),
+                                          Self::dot_mappings(l)));
+                    producer.insert(l.output.name.as_str(), elem(i));
+                }
+                Element::Register(r) => {
+                    out.push_str(&format!("  {} [shape=box,label=\"{} {} init={}\"];\n",
+                                          elem(i), Self::dot_escape(&r.output.name),
+                                          r.signal, r.init));
+                    producer.insert(r.output.name.as_str(), elem(i));
+                }
+            }
+        }
+
+        // edge from each signal's producer to every element that consumes it
+        for (i, el) in self.elements.iter().enumerate() {
+            let consumed: Vec<&Var> = match el {
+                Element::LUT(l) => l.inputs.iter().collect(),
+                Element::Register(r) => vec!(&r.input)
+            };
+            for v in consumed {
+                if let Some(src) = producer.get(v.name.as_str()) {
+                    out.push_str(&format!("  {} -> {} [label=\"{}\"];\n",
+                                          src, elem(i), Self::dot_escape(&v.name)));
+                }
+            }
+        }
+        // and drive the top-level outputs from their producers
+        for v in &self.outputs {
+            if let Some(src) = producer.get(v.name.as_str()) {
+                out.push_str(&format!("  {} -> {} [label=\"{}\"];\n",
+                                      src, node(&v.name), Self::dot_escape(&v.name)));
+            }
+        }
+
+        out
+    }
+
+    /// compact single-line rendering of a LUT's truth table for a DOT label
+    fn dot_mappings(lut: &LUT) -> String {
+        let mut rows: Vec<String> = lut.mappings.iter()
+            .map(|(k, v)| format!("{} {}",
+                                  k.iter().map(|b| b.to_string()).collect::<String>(), v))
+            .collect();
+        rows.sort();
+        rows.join("\\n")
+    }
+
+    /// escapes a signal name so it is safe inside a double-quoted DOT string
+    fn dot_escape(s: &str) -> String {
+        s.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    /// evaluates a single clock cycle. Registers form the cycle boundary: their
+    /// inputs are sampled up front into a pending next-state map, every
+    /// combinational LUT is then evaluated in dependency order so each reads
+    /// already-updated inputs, and the sampled register outputs are committed
+    /// last.
+    pub fn step(&self) -> Result<(), String> {
+
+        // pass 1: sample register inputs before any combinational logic runs
+        let mut pending: Vec<(&Register, u8)> = vec!();
+        for el in &self.elements {
+            if let Element::Register(r) = el {
+                let i = STATE.lock().unwrap().get(&r.input.name).copied().unwrap_or(0);
+                pending.push((r, i));
+            }
+        }
+
+        // pass 2: evaluate combinational LUTs in dependency order
+        let order = self.topo_order()?;
+        for idx in order {
+            if let Element::LUT(l) = &self.elements[idx] {
+                l.exec();
+            }
+        }
+
+        // pass 3: commit the registers at the cycle boundary, applying each
+        // one's clock/latch semantics (level latches read the data net as the
+        // LUT pass left it, the others their sampled input)
+        for (r, input) in pending {
+            r.exec(input);
+        }
+
+        Ok(())
+    }
 }
 
 /// Entry for getting FPGA configuration
@@ -173,6 +445,40 @@ impl Config {
         Config{models: Config::parse_blif(blif)}
     }
 
+    /// renders the whole configuration as one Graphviz `digraph`, emitting a
+    /// `subgraph` per model so designs with several models stay visually
+    /// separated.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph config {\n");
+        for (i, m) in self.models.iter().enumerate() {
+            out.push_str(&format!("  subgraph \"cluster_{}\" {{\n    label=\"{}\";\n",
+                                  i, Model::dot_escape(&m.name)));
+            out.push_str(&m.dot_body(&format!("m{}_", i)));
+            out.push_str("  }\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// runs the configuration for `cycles` clock cycles, stepping every model
+    /// once per cycle. Propagates the error from `Model::step` if a design
+    /// contains a combinational loop.
+    pub fn run(&self, cycles: usize) -> Result<(), String> {
+        for _ in 0..cycles {
+            self.step_once()?;
+        }
+        Ok(())
+    }
+
+    /// steps every model in the configuration once. Factored out of `run` so
+    /// the debugger can interleave breakpoint checks between cycles.
+    fn step_once(&self) -> Result<(), String> {
+        for m in &self.models {
+            m.step()?;
+        }
+        Ok(())
+    }
+
     /// parses blif-formatted data into comprising models
     pub fn parse_blif(mut input: &str) -> Vec<Model> {
 
@@ -208,6 +514,206 @@ impl Config {
 
 
 
+/// upper bound on the cycles a single `continue` advances, so an unreachable
+/// breakpoint cannot hang the single-threaded wasm UI.
+const CONTINUE_MAX_CYCLES: usize = 1_000_000;
+
+/// A halt condition for the debugger: either a named signal reaching a value or
+/// a target cycle count.
+#[derive(Debug, Eq, PartialEq)]
+enum Breakpoint {
+    Signal(String, u8),
+    Cycle(usize)
+}
+
+/// Interactive single-step debugger layered over the simulation loop. Drives a
+/// `Config` one cycle at a time, honouring signal/cycle breakpoints and signal
+/// watches, with a monitor-style command interface reachable from JS.
+#[wasm_bindgen]
+pub struct Debugger {
+    config: Config,
+    cycle: usize,
+    last_command: String,
+    repeat: usize,
+    trace_only: bool,
+    breakpoints: Vec<Breakpoint>,
+    watches: Vec<String>
+}
+
+#[wasm_bindgen]
+impl Debugger {
+
+    #[wasm_bindgen(constructor)]
+    pub fn new(blif: &str) -> Debugger {
+        Debugger {
+            config: Config::new(blif),
+            cycle: 0,
+            last_command: String::new(),
+            repeat: 1,
+            trace_only: false,
+            breakpoints: vec!(),
+            watches: vec!()
+        }
+    }
+
+    /// toggles tracing of every signal that changes during a cycle
+    pub fn trace(&mut self, on: bool) {
+        self.trace_only = on;
+    }
+
+    /// runs a monitor command and returns the text to show the user. An empty
+    /// command repeats the previous one `repeat` times, the way a CPU monitor
+    /// repeats on a bare return.
+    pub fn exec(&mut self, command: &str) -> String {
+        let command = command.trim();
+        if command.is_empty() {
+            // replay the previous command `repeat` times. The repeatable unit is
+            // a single cycle (`step n` stores `step` + `repeat = n`), so this is
+            // linear, not quadratic, and it stops as soon as a breakpoint fires
+            // instead of stepping past it.
+            let last = self.last_command.clone();
+            let count = self.repeat.max(1);
+            let mut out = String::new();
+            for _ in 0..count {
+                out.push_str(&self.dispatch(&last));
+                if self.hit_breakpoint().is_some() {
+                    break;
+                }
+            }
+            return out;
+        }
+        // a freshly typed command repeats once unless it declares otherwise
+        self.last_command = command.to_string();
+        self.repeat = 1;
+        self.dispatch(command)
+    }
+
+    fn dispatch(&mut self, command: &str) -> String {
+        let parts: Vec<&str> = command.split_whitespace().collect();
+        match parts.as_slice() {
+            ["step"] => { self.last_command = "step".to_string(); self.run(1) }
+            ["step", n] => {
+                let n = n.parse().unwrap_or(1);
+                // the repeatable unit is a single cycle, so a later bare return
+                // replays `step` `repeat` times rather than `step n` `n` times
+                self.repeat = n;
+                self.last_command = "step".to_string();
+                self.run(n)
+            }
+            ["continue"] | ["c"] => {
+                // `continue` runs until a breakpoint fires, but a missing or
+                // unreachable breakpoint must not spin forever and hang the
+                // single-threaded wasm UI, so refuse with no breakpoints set and
+                // otherwise cap the run at a bounded number of cycles.
+                if self.breakpoints.is_empty() {
+                    "no breakpoints set; nothing to continue to\n".to_string()
+                } else {
+                    self.run(CONTINUE_MAX_CYCLES)
+                }
+            }
+            ["break", bp] if bp.starts_with('@') => {
+                match bp[1..].parse() {
+                    Ok(c) => { self.breakpoints.push(Breakpoint::Cycle(c));
+                               format!("breakpoint at cycle {}\n", c) }
+                    Err(_) => format!("invalid cycle '{}'\n", &bp[1..])
+                }
+            }
+            ["break", sig, val] => {
+                match val.parse() {
+                    Ok(v) => { self.breakpoints.push(Breakpoint::Signal(sig.to_string(), v));
+                               format!("breakpoint when {} = {}\n", sig, v) }
+                    Err(_) => format!("invalid value '{}'\n", val)
+                }
+            }
+            ["watch", sig] => { self.watches.push(sig.to_string());
+                                format!("watching {}\n", sig) }
+            ["delete"] => { self.breakpoints.clear();
+                            "all breakpoints deleted\n".to_string() }
+            ["print", sig] => self.print(sig),
+            _ => format!("unknown command: {}\n", command)
+        }
+    }
+
+    /// advances up to `cycles` clock cycles, stopping early if a breakpoint
+    /// fires. Returns trace/watch output accumulated along the way.
+    fn run(&mut self, cycles: usize) -> String {
+        let mut out = String::new();
+        for _ in 0..cycles {
+            let before = if self.trace_only || !self.watches.is_empty() {
+                Some(STATE.lock().unwrap().clone())
+            } else {
+                None
+            };
+
+            if let Err(e) = self.config.step_once() {
+                out.push_str(&format!("error: {}\n", e));
+                return out;
+            }
+            self.cycle += 1;
+
+            if let Some(before) = before {
+                out.push_str(&self.report(&before));
+            }
+
+            if let Some(hit) = self.hit_breakpoint() {
+                out.push_str(&format!("stopped at cycle {}: {}\n", self.cycle, hit));
+                return out;
+            }
+        }
+        out
+    }
+
+    /// returns a description of the first breakpoint satisfied in the current
+    /// state, or `None` if execution should continue.
+    fn hit_breakpoint(&self) -> Option<String> {
+        let state = STATE.lock().unwrap();
+        for bp in &self.breakpoints {
+            match bp {
+                // `>=` so a breakpoint set at the current or an already-passed
+                // cycle still halts rather than being skipped over forever
+                Breakpoint::Cycle(c) if self.cycle >= *c => {
+                    return Some(format!("cycle {}", c));
+                }
+                Breakpoint::Signal(sig, val) => {
+                    if state.get(sig).copied() == Some(*val) {
+                        return Some(format!("{} = {}", sig, val));
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// emits watch values and, when tracing, every signal that changed this
+    /// cycle relative to `before`.
+    fn report(&self, before: &HashMap<String, u8>) -> String {
+        let state = STATE.lock().unwrap();
+        let mut out = String::new();
+        for sig in &self.watches {
+            out.push_str(&format!("  {} = {}\n", sig, state.get(sig).copied().unwrap_or(0)));
+        }
+        if self.trace_only {
+            let mut changed: Vec<(&String, u8)> = state.iter()
+                .filter(|(k, v)| before.get(*k) != Some(*v))
+                .map(|(k, v)| (k, *v))
+                .collect();
+            changed.sort();
+            for (sig, val) in changed {
+                out.push_str(&format!("  ~ {} = {}\n", sig, val));
+            }
+        }
+        out
+    }
+
+    fn print(&self, sig: &str) -> String {
+        match STATE.lock().unwrap().get(sig) {
+            Some(v) => format!("{} = {}\n", sig, v),
+            None => format!("{}: no such signal\n", sig)
+        }
+    }
+}
+
 named!(
     get_model_name<&str, &str>,
     do_parse!(
@@ -404,4 +910,88 @@ r#"
     if blif.len() != 2 {
         assert!(false, "wrong number models returned.");
     }
+}
+
+#[test]
+fn test_step_orders_combinational_logic() {
+    // c = b = a, but the consumer is listed before its producer so a single
+    // step only propagates a -> b -> c if the LUTs are topologically ordered
+    let m = Model::new("chain",
+                       vec!(Var::new("step_a")),
+                       vec!(Var::new("step_c")),
+                       vec!(
+                           Element::LUT(LUT::new(vec!("step_b"), "step_c", vec!("1 1"))),
+                           Element::LUT(LUT::new(vec!("step_a"), "step_b", vec!("1 1"))),
+                       ));
+    STATE.lock().unwrap().insert("step_a".to_string(), 1);
+    m.step().unwrap();
+    assert_eq!(*STATE.lock().unwrap().get("step_c").unwrap(), 1);
+}
+
+#[test]
+fn test_to_boolean_expr() {
+    let lut = LUT::new(vec!("out0", "out1", "out2"), "return0", vec!("011 1", "100 1"));
+    assert_eq!(lut.to_boolean_expr(),
+               "return0 = (~out0 & out1 & out2) | (out0 & ~out1 & ~out2)");
+}
+
+#[test]
+fn test_to_boolean_expr_dont_care() {
+    let lut = LUT::new(vec!("a", "b"), "y", vec!("1- 1"));
+    assert_eq!(lut.to_boolean_expr(), "y = (a)");
+}
+
+#[test]
+fn test_to_boolean_expr_empty() {
+    let lut = LUT::new(vec!("a"), "y", vec!());
+    assert_eq!(lut.to_boolean_expr(), "y = 0");
+}
+
+#[test]
+fn test_debugger_cycle_breakpoint() {
+    let mut dbg = Debugger::new(".model dbgm\n.inputs dbg_a\n.outputs dbg_c\n.names dbg_a dbg_c\n1 1\n");
+    dbg.exec("break @2");
+    let out = dbg.exec("step 5");
+    assert!(out.contains("stopped at cycle 2"));
+}
+
+#[test]
+fn test_to_dot_emits_digraph() {
+    let m = Model::new("dotmodel",
+                       vec!(Var::new("dot_a")),
+                       vec!(Var::new("dot_c")),
+                       vec!(Element::LUT(LUT::new(vec!("dot_a"), "dot_c", vec!("1 1")))));
+    let dot = m.to_dot();
+    assert!(dot.starts_with("digraph \"dotmodel\""));
+    assert!(dot.contains("->"));
+}
+
+#[test]
+fn test_register_rising_edge_latches() {
+    let reg = Register::new("reg_d", "reg_q", Some(("re", "reg_clk")), Some('0'));
+
+    // first evaluation after reset drives the output from init (0)
+    reg.exec(1);
+    assert_eq!(*STATE.lock().unwrap().get("reg_q").unwrap(), 0);
+
+    // control held low: no rising edge, the output holds
+    reg.exec(1);
+    assert_eq!(*STATE.lock().unwrap().get("reg_q").unwrap(), 0);
+
+    // 0 -> 1 transition on the control net latches the input
+    STATE.lock().unwrap().insert("reg_clk".to_string(), 1);
+    reg.exec(1);
+    assert_eq!(*STATE.lock().unwrap().get("reg_q").unwrap(), 1);
+}
+
+#[test]
+fn test_step_detects_combinational_cycle() {
+    let m = Model::new("loop",
+                       vec!(),
+                       vec!(),
+                       vec!(
+                           Element::LUT(LUT::new(vec!("cyc_y"), "cyc_x", vec!("1 1"))),
+                           Element::LUT(LUT::new(vec!("cyc_x"), "cyc_y", vec!("1 1"))),
+                       ));
+    assert!(m.step().is_err());
 }
\ No newline at end of file
@@ -7,7 +7,6 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::Clamped;
 use web_sys::{CanvasRenderingContext2d, ImageData};
-use core::sync::atomic::{ AtomicUsize, Ordering};
 use crate::util::*;
 
 const VGA_WIDTH: usize = 640+161;
@@ -15,7 +14,6 @@ const VGA_HEIGHT: usize = 480+44;
 const VGA_BUFFER_SIZE: usize = VGA_WIDTH * VGA_HEIGHT;
 
 
-pub static FRAME: AtomicUsize = AtomicUsize::new(0);
 //pub static POS_X: AtomicU32 = AtomicU32::new(0);
 //pub static POS_Y: AtomicU32 = AtomicU32::new(0);
 pub static mut BUFFER: [u32; VGA_BUFFER_SIZE] = [0; VGA_BUFFER_SIZE];
@@ -28,29 +26,92 @@ pub fn request_animation_frame(f: &Closure<dyn FnMut()>) {
 }
 
 
-// this is safe since buffer size is always within modified bounds
-pub unsafe fn test_render() {
-    let f = FRAME.fetch_add(1, Ordering::Relaxed) as usize;
-    let px = get_n_to_m("pixel", 0, 4);
-    // warn!("{:#?}", px);
+/// A single memory-mapped IO port binding named signal buses in `STATE` to the
+/// VGA framebuffer: an address bus decoded into a pixel offset, a data/colour
+/// bus, and a write-enable net that selects the direction each cycle.
+pub struct IoPort {
+    pub addr: String,
+    pub addr_bits: usize,
+    pub data: String,
+    pub data_bits: usize,
+    pub we: String,
+    /// number of pixels per row of the addressed region; the linear address
+    /// decodes to `x = addr % stride`, `y = addr / stride`
+    pub stride: usize,
+}
 
-    let mut color = 0xFF_00_00_00;
-    for i in 0..3 {
-        if px[i] == 1 {
-            color |= 0xFF << (i*8);
+/// Table of IO port bindings connecting a design's signal buses to video
+/// memory. Bindings are added in code with `bind`; none are read from the
+/// BLIF file.
+pub struct IoMap {
+    ports: Vec<IoPort>,
+}
+
+impl IoMap {
+    pub fn new() -> IoMap {
+        IoMap { ports: vec!() }
+    }
+
+    /// adds a port binding, returning self for chaining
+    pub fn bind(mut self, port: IoPort) -> IoMap {
+        self.ports.push(port);
+        self
+    }
+
+    /// drives one IO cycle for every bound port against the current `STATE` and
+    /// the framebuffer. This is safe since every computed index is bounds
+    /// checked against `VGA_BUFFER_SIZE`.
+    pub unsafe fn render(&self) {
+        for p in &self.ports {
+            // a zero stride cannot describe a row; skip the malformed port
+            if p.stride == 0 {
+                continue;
+            }
+            let addr = bits_to_usize(&get_n_to_m(&p.addr, 0, p.addr_bits));
+            // decode the linear address into 2-D framebuffer coordinates: the
+            // port's row is `stride` pixels wide, so rows may be narrower than
+            // the full scanline and wrap onto the next line of video memory
+            let x = addr % p.stride;
+            let y = addr / p.stride;
+            // skip coordinates that fall outside a scanline or past the buffer
+            let idx = y * VGA_WIDTH + x;
+            if x >= VGA_WIDTH || idx >= VGA_BUFFER_SIZE {
+                continue;
+            }
+            if get!(&p.we) == 1 {
+                // write: pack the data bus into the addressed pixel
+                BUFFER[idx] = pack_color(&get_n_to_m(&p.data, 0, p.data_bits));
+            } else {
+                // read: drive the data bus back from video memory
+                set_n_to_m(&p.data, 0, p.data_bits, unpack_color(BUFFER[idx], p.data_bits));
+            }
         }
     }
-    // warn!("{:#?}", color);
-    BUFFER[f] = color;
+}
 
-    FRAME.compare_and_swap(VGA_BUFFER_SIZE, 0, Ordering::Relaxed);
+/// interprets a little-endian bit vector (bit 0 first) as an integer
+fn bits_to_usize(bits: &[u8]) -> usize {
+    bits.iter().enumerate().fold(0, |acc, (i, &b)| acc | ((b as usize) << i))
+}
+
+/// packs a colour bus into an ARGB pixel, one opaque channel per set bit, the
+/// same scheme the original fixed renderer used
+fn pack_color(data: &[u8]) -> u32 {
+    let mut color = 0xFF_00_00_00;
+    for i in 0..data.len().min(3) {
+        if data[i] == 1 {
+            color |= 0xFF << (i * 8);
+        }
+    }
+    color
+}
 
-    // for y in 0..VGA_HEIGHT {
-    //     for x in 0..VGA_WIDTH {
-    //         BUFFER[y * VGA_WIDTH + x] = color
-    //             // f.wrapping_add((x^y) as u32) | 0xFF_00_00_00;
-    //     }
-    // }
+/// recovers a `data_bits`-wide colour bus from an ARGB pixel, the inverse of
+/// `pack_color`
+fn unpack_color(color: u32, data_bits: usize) -> Vec<u8> {
+    (0..data_bits)
+        .map(|i| if i < 3 && (color >> (i * 8)) & 0xFF != 0 { 1 } else { 0 })
+        .collect()
 }
 
 pub fn draw(
@@ -61,3 +122,42 @@ pub fn draw(
     let data = ImageData::new_with_u8_clamped_array_and_sh(Clamped(u8_buf), VGA_WIDTH as u32, VGA_HEIGHT as u32)?;
     ctx.put_image_data(&data, 0.0, 0.0)
 }
+
+#[test]
+fn test_iob_write_then_read() {
+    use crate::config::STATE;
+
+    // declare a 4-pixel-wide port with a 3-bit address and 3-bit colour bus
+    let port = IoPort {
+        addr: "addr".to_string(), addr_bits: 3,
+        data: "data".to_string(), data_bits: 3,
+        we: "we".to_string(), stride: 4,
+    };
+    let io = IoMap::new().bind(port);
+
+    // address 6 decodes to (x=2, y=1) -> BUFFER[1*VGA_WIDTH + 2]
+    let idx = VGA_WIDTH + 2;
+    {
+        let mut s = STATE.lock().unwrap();
+        for b in 0..3 {
+            s.insert(format!("addr[{}]", b), (6 >> b) & 1);
+            s.insert(format!("data[{}]", b), 0);
+        }
+        // addr = 0b110 = 6, data = 0b101 (red + blue), write enabled
+        s.insert("data[0]".to_string(), 1);
+        s.insert("data[2]".to_string(), 1);
+        s.insert("we".to_string(), 1);
+    }
+
+    unsafe { io.render(); }
+    assert_eq!(unsafe { BUFFER[idx] }, pack_color(&[1, 0, 1]));
+
+    // now read the same pixel back onto the data bus with write disabled
+    {
+        let mut s = STATE.lock().unwrap();
+        s.insert("we".to_string(), 0);
+        for b in 0..3 { s.insert(format!("data[{}]", b), 0); }
+    }
+    unsafe { io.render(); }
+    assert_eq!(get_n_to_m("data", 0, 3), vec!(1, 0, 1));
+}